@@ -0,0 +1,165 @@
+// Companion proc-macro crate for the `Expr`/`Stmt` AST. It expands
+//
+//     define_ast! {
+//         Expr {
+//             Binary(left: Box<Expr>, operator: Token, right: Box<Expr>),
+//             ...
+//         }
+//     }
+//
+// into the struct-variant enum, an `accept`/`span` impl, and a `Visitor<R>`
+// trait with one `visit_<variant>_<expression|stmt>` method per variant —
+// the same shapes that used to be hand-written (and kept in sync by hand)
+// in `expressions.rs` and `statement.rs`.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, parenthesized, parse_macro_input, Ident, Token, Type};
+
+struct Field {
+    name: Ident,
+    ty: Type,
+}
+
+impl Parse for Field {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty: Type = input.parse()?;
+        Ok(Field { name, ty })
+    }
+}
+
+struct Variant {
+    name: Ident,
+    fields: Vec<Field>,
+}
+
+impl Parse for Variant {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+
+        let content;
+        parenthesized!(content in input);
+        let fields = Punctuated::<Field, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+
+        Ok(Variant { name, fields })
+    }
+}
+
+struct AstDef {
+    name: Ident,
+    variants: Vec<Variant>,
+}
+
+impl Parse for AstDef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let variants = Punctuated::<Variant, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+
+        Ok(AstDef { name, variants })
+    }
+}
+
+// `visit_<variant, with any trailing root-name suffix stripped>_<suffix>`,
+// e.g. root `Expr`/variant `LiteralExpr` -> `visit_literal_expression`,
+// root `Stmt`/variant `Print` -> `visit_print_stmt`.
+fn visit_method_name(root: &Ident, variant: &Ident, suffix: &str) -> Ident {
+    let root_name = root.to_string();
+    let mut variant_name = variant.to_string();
+    if variant_name != root_name && variant_name.ends_with(&root_name) {
+        variant_name.truncate(variant_name.len() - root_name.len());
+    }
+
+    let mut snake = String::new();
+    for (i, ch) in variant_name.char_indices() {
+        if i > 0 && ch.is_uppercase() {
+            snake.push('_');
+        }
+        snake.push(ch.to_ascii_lowercase());
+    }
+
+    format_ident!("visit_{}_{}", snake, suffix)
+}
+
+fn expand(ast: &AstDef) -> proc_macro2::TokenStream {
+    let enum_name = &ast.name;
+    let suffix = if enum_name == "Expr" { "expression" } else { "stmt" };
+    let span_field: Field = Field { name: format_ident!("span"), ty: syn::parse_quote!((usize, usize)) };
+
+    let variant_defs = ast.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let fields = variant.fields.iter().chain(std::iter::once(&span_field)).map(|f| {
+            let name = &f.name;
+            let ty = &f.ty;
+            quote! { #name: #ty }
+        });
+        quote! { #variant_name { #(#fields),* } }
+    });
+
+    let accept_arms = ast.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        let method = visit_method_name(enum_name, variant_name, suffix);
+        quote! { #enum_name::#variant_name { .. } => visitor.#method(self) }
+    });
+
+    let span_arms = ast.variants.iter().map(|variant| {
+        let variant_name = &variant.name;
+        quote! { #enum_name::#variant_name { span, .. } }
+    });
+
+    let visitor_name = if enum_name == "Expr" {
+        format_ident!("Visitor", span = Span::call_site())
+    } else {
+        format_ident!("{}Visitor", enum_name, span = Span::call_site())
+    };
+    let visitor_methods = ast.variants.iter().map(|variant| {
+        let method = visit_method_name(enum_name, &variant.name, suffix);
+        quote! { fn #method(&self, node: &#enum_name) -> R; }
+    });
+
+    quote! {
+        #[derive(Clone)]
+        pub enum #enum_name {
+            #(#variant_defs),*
+        }
+
+        impl #enum_name {
+            pub fn accept<R>(&self, visitor: &impl #visitor_name<R>) -> R {
+                match self {
+                    #(#accept_arms),*
+                }
+            }
+
+            // Byte-range (start, end) of this node in the original source,
+            // merged from its tokens/sub-nodes at construction time.
+            pub fn span(&self) -> (usize, usize) {
+                match self {
+                    #(#span_arms)|* => *span,
+                }
+            }
+        }
+
+        pub trait #visitor_name<R> {
+            #(#visitor_methods)*
+        }
+    }
+}
+
+#[proc_macro]
+pub fn define_ast(input: TokenStream) -> TokenStream {
+    let ast_def = parse_macro_input!(input as AstDef);
+    expand(&ast_def).into()
+}
@@ -1,13 +1,29 @@
 use crate::expressions::{Expr, Visitor};
+use crate::lexer::{Literal, Token, TokenType};
+use crate::statement::{Stmt, StmtVisitor};
 
-struct AstPrinter {}
+pub(crate) struct AstPrinter {}
 
-// TODO: Implement complete printer later on...
 impl AstPrinter {
-    fn print(&self, expr: &Expr) {
+    pub(crate) fn print(&self, expr: &Expr) {
         println!("{}", expr.accept(self));
     }
 
+    pub(crate) fn to_string(&self, expr: &Expr) -> String {
+        expr.accept(self)
+    }
+
+    pub(crate) fn stmt_to_string(&self, stmt: &Stmt) -> String {
+        stmt.accept(self)
+    }
+
+    // Serializes a whole program to the canonical parenthesized dump format,
+    // one top-level form per statement, so it can round-trip through
+    // `parse_program_dump` as a golden-file snapshot.
+    pub(crate) fn print_program(&self, statements: &[Stmt]) -> String {
+        statements.iter().map(|stmt| self.stmt_to_string(stmt)).collect::<Vec<_>>().join("\n")
+    }
+
     fn parenthesize(&self, name: &str, arguments: Vec<&Box<Expr>>) -> String {
         let mut builder = String::new();
 
@@ -27,64 +43,409 @@ impl AstPrinter {
 impl Visitor<String> for AstPrinter {
     fn visit_binary_expression(&self, expr: &Expr) -> String {
         match expr {
-            Expr::Binary { left, right, operator } => self.parenthesize(operator.lexeme.as_str(), vec![left, right]),
+            Expr::Binary { left, right, operator, .. } => self.parenthesize(operator.lexeme.as_str(), vec![left, right]),
             _ => panic!("Fudeu")
         }
     }
     fn visit_literal_expression(&self, expr: &Expr) -> String {
         match expr {
-            Expr::LiteralExpr { value } => value.to_string(),
+            Expr::LiteralExpr { value, .. } => value.to_string(),
             _ => panic!("Fudeu 2")
         }
     }
 
     fn visit_assign_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::Assign { name, value, .. } => format!("(= {} {})", name.lexeme, value.accept(self)),
+            _ => panic!("Wrong type")
+        }
     }
 
     fn visit_call_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::Call { callee, arguments, .. } => {
+                let mut builder = format!("(call {}", callee.accept(self));
+                arguments.iter().for_each(|arg| {
+                    builder.push(' ');
+                    builder.push_str(arg.accept(self).as_str());
+                });
+                builder.push(')');
+                builder
+            }
+            _ => panic!("Wrong type")
+        }
     }
 
     fn visit_get_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::Get { object, name, .. } => format!("(. {} {})", object.accept(self), name.lexeme),
+            _ => panic!("Wrong type")
+        }
     }
 
     fn visit_grouping_expression(&self, expr: &Expr) -> String {
         match expr {
-            Expr::Grouping { expression } => self.parenthesize("group", vec!(expression)),
+            Expr::Grouping { expression, .. } => self.parenthesize("group", vec!(expression)),
             _ => panic!("Wrong type")
         }
     }
 
     fn visit_logical_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::Logical { left, right, operator, .. } => self.parenthesize(operator.lexeme.as_str(), vec![left, right]),
+            _ => panic!("Wrong type")
+        }
     }
 
     fn visit_set_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::Set { object, name, value, .. } => format!("(set (. {} {}) {})", object.accept(self), name.lexeme, value.accept(self)),
+            _ => panic!("Wrong type")
+        }
     }
 
     fn visit_super_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::Super { method, .. } => format!("(super {})", method.lexeme),
+            _ => panic!("Wrong type")
+        }
     }
 
     fn visit_this_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::This { .. } => String::from("this"),
+            _ => panic!("Wrong type")
+        }
     }
 
     fn visit_unary_expression(&self, expr: &Expr) -> String {
         match expr {
-            Expr::Unary { right, operator } => self.parenthesize(operator.lexeme.as_str(), vec!(right)),
+            Expr::Unary { right, operator, .. } => self.parenthesize(operator.lexeme.as_str(), vec!(right)),
             _ => panic!("Wrong...")
         }
     }
 
     fn visit_variable_expression(&self, expr: &Expr) -> String {
-        String::new()
+        match expr {
+            Expr::Variable { name, .. } => name.lexeme.clone(),
+            _ => panic!("Wrong type")
+        }
+    }
+}
+
+impl StmtVisitor<String> for AstPrinter {
+    fn visit_print_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Print { expression, .. } => format!("(print {})", expression.accept(self)),
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_block_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Block { statements, .. } => {
+                let mut builder = String::from("(block");
+                statements.iter().for_each(|s| {
+                    builder.push(' ');
+                    builder.push_str(s.accept(self).as_str());
+                });
+                builder.push(')');
+                builder
+            }
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_expression_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Expression { expression, .. } => format!("(; {})", expression.accept(self)),
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_while_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::While { condition, body, .. } => format!("(while {} {})", condition.accept(self), body.accept(self)),
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_return_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Return { value, .. } => format!("(return {})", value.accept(self)),
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_variable_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Variable { name, initializer, .. } => format!("(var {} {})", name.lexeme, initializer.accept(self)),
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_if_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::If { condition, then_branch, else_branch, .. } => {
+                format!("(if {} {} {})", condition.accept(self), then_branch.accept(self), else_branch.accept(self))
+            }
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_function_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Function { name, params, body, .. } => {
+                let params = params.iter().map(|p| p.lexeme.as_str()).collect::<Vec<_>>().join(" ");
+                let mut builder = format!("(fun {} ({})", name.lexeme, params);
+                body.iter().for_each(|s| {
+                    builder.push(' ');
+                    builder.push_str(s.accept(self).as_str());
+                });
+                builder.push(')');
+                builder
+            }
+            _ => panic!("Wrong type")
+        }
+    }
+
+    fn visit_class_stmt(&self, stmt: &Stmt) -> String {
+        match stmt {
+            Stmt::Class { name, super_class, methods, .. } => {
+                let mut builder = format!("(class {} {}", name.lexeme, super_class.accept(self));
+                methods.iter().for_each(|m| {
+                    builder.push(' ');
+                    builder.push_str(m.accept(self).as_str());
+                });
+                builder.push(')');
+                builder
+            }
+            _ => panic!("Wrong type")
+        }
     }
 }
 
+// A reader for the S-expression dump format emitted by `AstPrinter`, so the
+// format round-trips (e.g. for the precedence tests and golden-file
+// snapshots). Bare identifiers are ambiguous between a `Variable` and a
+// string literal, since `visit_literal_expression` prints strings unquoted
+// (see the `string_literal_binary` test below) — like the printer itself, this
+// reader is meant for debugging/tests, not as a faithful serializer for
+// arbitrary string data.
+enum Sexpr {
+    Atom(String),
+    List(Vec<Sexpr>),
+}
+
+fn read_sexpr(chars: &[char], pos: &mut usize) -> Sexpr {
+    skip_whitespace(chars, pos);
+
+    if chars[*pos] == '(' {
+        *pos += 1;
+        let mut items = Vec::new();
+        loop {
+            skip_whitespace(chars, pos);
+            if chars[*pos] == ')' {
+                *pos += 1;
+                break;
+            }
+            items.push(read_sexpr(chars, pos));
+        }
+        Sexpr::List(items)
+    } else {
+        Sexpr::Atom(read_atom(chars, pos))
+    }
+}
+
+fn skip_whitespace(chars: &[char], pos: &mut usize) {
+    while *pos < chars.len() && chars[*pos].is_whitespace() {
+        *pos += 1;
+    }
+}
+
+fn read_atom(chars: &[char], pos: &mut usize) -> String {
+    skip_whitespace(chars, pos);
+    let start = *pos;
+    while *pos < chars.len() && chars[*pos] != '(' && chars[*pos] != ')' && !chars[*pos].is_whitespace() {
+        *pos += 1;
+    }
+    chars[start..*pos].iter().collect()
+}
+
+fn synthetic_token(token_type: TokenType, lexeme: &str) -> Token {
+    Token::new(token_type, lexeme, Literal::Nil, 0, (0, 0))
+}
+
+fn binary_operator_type(op: &str) -> TokenType {
+    match op {
+        "+" => TokenType::PLUS,
+        "-" => TokenType::MINUS,
+        "*" => TokenType::STAR,
+        "/" => TokenType::SLASH,
+        ">" => TokenType::GREATER,
+        ">=" => TokenType::GREATER_EQUAL,
+        "<" => TokenType::LESS,
+        "<=" => TokenType::LESS_EQUAL,
+        "==" => TokenType::EQUAL_EQUAL,
+        "!=" => TokenType::BANG_EQUAL,
+        other => panic!("unknown binary operator '{}'", other),
+    }
+}
+
+fn atom_to_expr(atom: &str) -> Expr {
+    match atom {
+        "true" => Expr::LiteralExpr { value: Literal::Bool(true), span: (0, 0) },
+        "false" => Expr::LiteralExpr { value: Literal::Bool(false), span: (0, 0) },
+        "nil" => Expr::LiteralExpr { value: Literal::Nil, span: (0, 0) },
+        "this" => Expr::This { keyword: synthetic_token(TokenType::THIS, "this"), span: (0, 0) },
+        atom => match atom.parse::<f32>() {
+            Ok(value) => {
+                let precision = atom.split_once('.').map(|(_, frac)| frac.len()).unwrap_or(0);
+                Expr::LiteralExpr { value: Literal::Number(value, precision), span: (0, 0) }
+            }
+            Err(_) => Expr::Variable { name: synthetic_token(TokenType::IDENTIFIER, atom), span: (0, 0) },
+        },
+    }
+}
+
+fn list_to_expr(items: &[Sexpr]) -> Expr {
+    let head = match &items[0] {
+        Sexpr::Atom(head) => head.as_str(),
+        Sexpr::List(_) => panic!("expected an operator atom in head position"),
+    };
+    let args: Vec<Expr> = items[1..].iter().map(sexpr_to_expr).collect();
+
+    match head {
+        "group" => Expr::Grouping { expression: Box::new(args.into_iter().next().unwrap()), span: (0, 0) },
+        "call" => {
+            let mut args = args.into_iter();
+            let callee = Box::new(args.next().unwrap());
+            let arguments = args.map(Box::new).collect();
+            Expr::Call { callee, paren: synthetic_token(TokenType::RIGHT_PAREN, ")"), arguments, span: (0, 0) }
+        }
+        "." => {
+            let mut args = args.into_iter();
+            let object = Box::new(args.next().unwrap());
+            let Expr::Variable { name, .. } = args.next().unwrap() else { panic!("expected a property name") };
+            Expr::Get { object, name, span: (0, 0) }
+        }
+        "set" => {
+            let mut args = args.into_iter();
+            let Expr::Get { object, name, .. } = args.next().unwrap() else { panic!("expected a `(. object name)` set target") };
+            let value = Box::new(args.next().unwrap());
+            Expr::Set { object, name, value, span: (0, 0) }
+        }
+        "=" => {
+            let mut args = args.into_iter();
+            let Expr::Variable { name, .. } = args.next().unwrap() else { panic!("expected an assignment target") };
+            let value = Box::new(args.next().unwrap());
+            Expr::Assign { name, value, span: (0, 0) }
+        }
+        "super" => {
+            let Expr::Variable { name: method, .. } = args.into_iter().next().unwrap() else { panic!("expected a method name") };
+            Expr::Super { keyword: synthetic_token(TokenType::SUPER, "super"), method, span: (0, 0) }
+        }
+        "this" => Expr::This { keyword: synthetic_token(TokenType::THIS, "this"), span: (0, 0) },
+        "!" => Expr::Unary { operator: synthetic_token(TokenType::BANG, "!"), right: Box::new(args.into_iter().next().unwrap()), span: (0, 0) },
+        "-" if args.len() == 1 => {
+            Expr::Unary { operator: synthetic_token(TokenType::MINUS, "-"), right: Box::new(args.into_iter().next().unwrap()), span: (0, 0) }
+        }
+        "or" | "and" => {
+            let mut args = args.into_iter();
+            let left = Box::new(args.next().unwrap());
+            let right = Box::new(args.next().unwrap());
+            let operator = synthetic_token(if head == "or" { TokenType::OR } else { TokenType::AND }, head);
+            Expr::Logical { left, operator, right, span: (0, 0) }
+        }
+        op => {
+            let mut args = args.into_iter();
+            let left = Box::new(args.next().unwrap());
+            let right = Box::new(args.next().unwrap());
+            Expr::Binary { left, operator: synthetic_token(binary_operator_type(op), op), right, span: (0, 0) }
+        }
+    }
+}
+
+fn sexpr_to_expr(sexpr: &Sexpr) -> Expr {
+    match sexpr {
+        Sexpr::Atom(atom) => atom_to_expr(atom),
+        Sexpr::List(items) => list_to_expr(items),
+    }
+}
+
+fn sexpr_to_stmt(sexpr: &Sexpr) -> Stmt {
+    let Sexpr::List(items) = sexpr else { panic!("expected a statement form, found a bare atom") };
+    let Sexpr::Atom(head) = &items[0] else { panic!("expected a keyword atom in head position") };
+
+    match head.as_str() {
+        "print" => Stmt::Print { expression: sexpr_to_expr(&items[1]), span: (0, 0) },
+        ";" => Stmt::Expression { expression: sexpr_to_expr(&items[1]), span: (0, 0) },
+        "block" => Stmt::Block { statements: items[1..].iter().map(sexpr_to_stmt).collect(), span: (0, 0) },
+        "while" => Stmt::While { condition: sexpr_to_expr(&items[1]), body: Box::new(sexpr_to_stmt(&items[2])), span: (0, 0) },
+        "return" => Stmt::Return { keyword: synthetic_token(TokenType::RETURN, "return"), value: sexpr_to_expr(&items[1]), span: (0, 0) },
+        "var" => {
+            let Sexpr::Atom(name) = &items[1] else { panic!("expected a variable name") };
+            Stmt::Variable { name: synthetic_token(TokenType::IDENTIFIER, name), initializer: sexpr_to_expr(&items[2]), span: (0, 0) }
+        }
+        "if" => Stmt::If {
+            condition: sexpr_to_expr(&items[1]),
+            then_branch: Box::new(sexpr_to_stmt(&items[2])),
+            else_branch: Box::new(sexpr_to_stmt(&items[3])),
+            span: (0, 0),
+        },
+        "fun" => {
+            let Sexpr::Atom(name) = &items[1] else { panic!("expected a function name") };
+            let Sexpr::List(param_list) = &items[2] else { panic!("expected a parameter list") };
+            let params = param_list
+                .iter()
+                .map(|p| {
+                    let Sexpr::Atom(p) = p else { panic!("expected a parameter name") };
+                    synthetic_token(TokenType::IDENTIFIER, p)
+                })
+                .collect();
+            let body = items[3..].iter().map(sexpr_to_stmt).collect();
+            Stmt::Function { name: synthetic_token(TokenType::IDENTIFIER, name), params, body, span: (0, 0) }
+        }
+        "class" => {
+            let Sexpr::Atom(name) = &items[1] else { panic!("expected a class name") };
+            let methods = items[3..].iter().map(sexpr_to_stmt).collect();
+            Stmt::Class {
+                name: synthetic_token(TokenType::IDENTIFIER, name),
+                super_class: sexpr_to_expr(&items[2]),
+                methods,
+                span: (0, 0),
+            }
+        }
+        other => panic!("unknown statement form '{}'", other),
+    }
+}
+
+// Parses a single expression from the dump format, e.g. for the precedence
+// round-trip test.
+pub(crate) fn parse_expr_dump(source: &str) -> Expr {
+    let chars: Vec<char> = source.chars().collect();
+    let mut pos = 0;
+    sexpr_to_expr(&read_sexpr(&chars, &mut pos))
+}
+
+// Parses a whole program dumped by `AstPrinter::print_program` back into
+// its statements.
+pub(crate) fn parse_program_dump(source: &str) -> Vec<Stmt> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut pos = 0;
+    let mut statements = Vec::new();
+
+    loop {
+        skip_whitespace(&chars, &mut pos);
+        if pos >= chars.len() {
+            break;
+        }
+        statements.push(sexpr_to_stmt(&read_sexpr(&chars, &mut pos)));
+    }
+
+    statements
+}
+
 #[cfg(test)]
 mod tests {
     use crate::expressions::Expr::{Binary, Grouping, LiteralExpr, Unary};
@@ -95,40 +456,88 @@ mod tests {
     #[test]
     fn string_literal_binary() {
         let visitor = AstPrinter {};
-        let l1 = LiteralExpr { value: Literal::String(String::from("Shermak")) };
-        let l2 = LiteralExpr { value: Literal::String(String::from("Jaw")) };
+        let l1 = LiteralExpr { value: Literal::String(String::from("Shermak")), span: (0, 9) };
+        let l2 = LiteralExpr { value: Literal::String(String::from("Jaw")), span: (12, 17) };
         let bi = Binary {
             left: Box::new(l1),
-            operator: Token::new(TokenType::PLUS, "+", Literal::String(String::from("+")), 1),
+            operator: Token::new(TokenType::PLUS, "+", Literal::String(String::from("+")), 1, (10, 11)),
             right: Box::new(l2),
+            span: (0, 17),
         };
 
         visitor.print(&bi);
         assert_eq!("(+ Shermak Jaw)", bi.accept(&visitor));
     }
 
+    #[test]
+    fn statement_forms_print_as_expected() {
+        let printer = AstPrinter {};
+        let x = synthetic_token(TokenType::IDENTIFIER, "x");
+
+        let print_stmt = Stmt::Print { expression: atom_to_expr("x"), span: (0, 0) };
+        assert_eq!("(print x)", printer.stmt_to_string(&print_stmt));
+
+        let var_stmt = Stmt::Variable { name: x.clone(), initializer: atom_to_expr("1"), span: (0, 0) };
+        assert_eq!("(var x 1)", printer.stmt_to_string(&var_stmt));
+
+        let while_stmt = Stmt::While {
+            condition: list_to_expr(&[Sexpr::Atom("<".into()), Sexpr::Atom("x".into()), Sexpr::Atom("10".into())]),
+            body: Box::new(Stmt::Expression { expression: atom_to_expr("x"), span: (0, 0) }),
+            span: (0, 0),
+        };
+        assert_eq!("(while (< x 10) (; x))", printer.stmt_to_string(&while_stmt));
+
+        let class_stmt = Stmt::Class {
+            name: synthetic_token(TokenType::IDENTIFIER, "Pastry"),
+            super_class: Expr::LiteralExpr { value: Literal::Nil, span: (0, 0) },
+            methods: vec![Stmt::Function { name: synthetic_token(TokenType::IDENTIFIER, "cook"), params: vec![], body: vec![], span: (0, 0) }],
+            span: (0, 0),
+        };
+        assert_eq!("(class Pastry nil (fun cook ()))", printer.stmt_to_string(&class_stmt));
+    }
+
+    #[test]
+    fn program_dump_round_trips_through_the_reader() {
+        let dump = "(var x 1)\n(print x)\n(while (< x 10) (block (print x) (; (= x (+ x 1)))))";
+        let statements = parse_program_dump(dump);
+        let printer = AstPrinter {};
+        assert_eq!(dump, printer.print_program(&statements));
+    }
+
+    #[test]
+    fn function_dump_round_trips_through_the_reader() {
+        let dump = "(fun add (a b) (return (+ a b)))";
+        let statements = parse_program_dump(dump);
+        let printer = AstPrinter {};
+        assert_eq!(dump, printer.print_program(&statements));
+    }
+
     #[test]
     fn book_example() {
         let visitor = AstPrinter {};
         let unary = Unary {
-            right: Box::from(LiteralExpr { value: Literal::Number(123_f32, 0) }),
+            right: Box::from(LiteralExpr { value: Literal::Number(123_f32, 0), span: (1, 4) }),
             operator: Token::new(
                 TokenType::MINUS,
                 String::from("-").as_str(),
                 Literal::String(String::from("-")),
                 1,
-            )
+                (0, 1),
+            ),
+            span: (0, 4),
         };
         let grouping = Grouping {
-            expression: Box::from(LiteralExpr { value: Literal::Number(45.67, 2) })
+            expression: Box::from(LiteralExpr { value: Literal::Number(45.67, 2), span: (8, 13) }),
+            span: (7, 14),
         };
         let bi = Binary {
-            operator: Token::new(TokenType::STAR, "*", Literal::String(String::from("*")), 1),
+            operator: Token::new(TokenType::STAR, "*", Literal::String(String::from("*")), 1, (5, 6)),
             left: Box::new(unary),
             right: Box::new(grouping),
+            span: (0, 14),
         };
 
         visitor.print(&bi);
         assert_eq!("(* (- 123) (group 45.67))", bi.accept(&visitor));
     }
-}
\ No newline at end of file
+}
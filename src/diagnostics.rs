@@ -0,0 +1,135 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+// A single reportable problem, pinned to a byte-range span in the source.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: (usize, usize),
+    pub labels: Vec<((usize, usize), String)>,
+    pub severity: Severity,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>, span: (usize, usize)) -> Diagnostic {
+        Diagnostic { message: message.into(), span, labels: Vec::new(), severity }
+    }
+
+    pub fn error(message: impl Into<String>, span: (usize, usize)) -> Diagnostic {
+        Diagnostic::new(Severity::Error, message, span)
+    }
+
+    pub fn warning(message: impl Into<String>, span: (usize, usize)) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, message, span)
+    }
+
+    pub fn with_label(mut self, span: (usize, usize), message: impl Into<String>) -> Diagnostic {
+        self.labels.push((span, message.into()));
+        self
+    }
+}
+
+// Locates the 1-based line/column and the byte range of the line containing
+// `offset`, by scanning for the surrounding newlines.
+fn locate(source: &str, offset: usize) -> (usize, usize, usize, usize) {
+    let offset = offset.min(source.len());
+    let mut line = 1;
+    let mut line_start = 0;
+
+    for (i, ch) in source.char_indices() {
+        if i >= offset {
+            break;
+        }
+        if ch == '\n' {
+            line += 1;
+            line_start = i + 1;
+        }
+    }
+
+    let line_end = source[line_start..].find('\n').map(|i| line_start + i).unwrap_or(source.len());
+    let column = offset - line_start + 1;
+
+    (line, column, line_start, line_end)
+}
+
+fn render_underline(source: &str, span: (usize, usize), message: &str) -> String {
+    let (line, column, line_start, line_end) = locate(source, span.0);
+    let text = &source[line_start..line_end];
+    let underline_len = span.1.saturating_sub(span.0).max(1);
+
+    format!(
+        "{:>4} | {}\n     | {}^{} {}\n",
+        line,
+        text,
+        " ".repeat(column - 1),
+        "~".repeat(underline_len - 1),
+        message,
+    )
+}
+
+// Renders a caret-underlined report of `diagnostic` against the original
+// `source`, in the style of ariadne/rustc diagnostics: the message, the
+// offending line, and a `^~~~` underline under the exact span, followed by
+// any secondary labels.
+pub fn render(source: &str, diagnostic: &Diagnostic) -> String {
+    let (line, column, ..) = locate(source, diagnostic.span.0);
+    let mut out = format!("{}: {}\n  --> line {}:{}\n", diagnostic.severity, diagnostic.message, line, column);
+
+    out.push_str(&render_underline(source, diagnostic.span, "here"));
+
+    for (span, label) in &diagnostic.labels {
+        out.push_str(&render_underline(source, *span, label));
+    }
+
+    out
+}
+
+// Collects diagnostics emitted during a single run (scanning, parsing,
+// evaluating) so they can all be reported together instead of aborting on
+// the first error.
+#[derive(Debug, Default)]
+pub struct DiagnosticSink {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticSink {
+    pub fn new() -> DiagnosticSink {
+        DiagnosticSink { diagnostics: Vec::new() }
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.severity == Severity::Error)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter()
+    }
+
+    pub fn report(&self, source: &str) {
+        for diagnostic in &self.diagnostics {
+            eprint!("{}", render(source, diagnostic));
+        }
+    }
+}
@@ -1,54 +1,63 @@
 use crate::lexer::{Literal, Token};
+use define_ast_macro::define_ast;
 
 type ExprArguments = Vec<Box<Expr>>;
 
-
-pub enum Expr {
-    Assign { name: Token, value: Box<Expr> },
-    LiteralExpr { value: Literal },
-    Binary { left: Box<Expr>, operator: Token, right: Box<Expr> },
-    Call { callee: Box<Expr>, paren: Token, arguments: ExprArguments },
-    Get { object: Box<Expr>, name: Token },
-    Grouping { expression: Box<Expr> },
-    Logical { left: Box<Expr>, right: Box<Expr>, operator: Token },
-    Set { object: Box<Expr>, name: Token, value: Box<Expr> },
-    Super { keyword: Token, method: Token },
-    This { keyword: Token },
-    Unary { operator: Token, right: Box<Expr> },
-    Variable { name: Token },
+define_ast! {
+    Expr {
+        Assign(name: Token, value: Box<Expr>),
+        LiteralExpr(value: Literal),
+        Binary(left: Box<Expr>, operator: Token, right: Box<Expr>),
+        Call(callee: Box<Expr>, paren: Token, arguments: ExprArguments),
+        Get(object: Box<Expr>, name: Token),
+        Grouping(expression: Box<Expr>),
+        Logical(left: Box<Expr>, right: Box<Expr>, operator: Token),
+        Set(object: Box<Expr>, name: Token, value: Box<Expr>),
+        Super(keyword: Token, method: Token),
+        This(keyword: Token),
+        Unary(operator: Token, right: Box<Expr>),
+        Variable(name: Token),
+    }
 }
 
 impl Expr {
-    pub fn accept<R>(&self, expr_visitor: &impl Visitor<R>) -> R {
-        match self {
-            Expr::Binary { .. } => expr_visitor.visit_binary_expression(self),
-            Expr::LiteralExpr { .. } => expr_visitor.visit_literal_expression(self),
-            Expr::Assign { .. } => expr_visitor.visit_assign_expression(self),
-            Expr::Call { .. } => expr_visitor.visit_call_expression(self),
-            Expr::Get { .. } => expr_visitor.visit_get_expression(self),
-            Expr::Grouping { .. } => expr_visitor.visit_grouping_expression(self),
-            Expr::Logical { .. } => expr_visitor.visit_logical_expression(self),
-            Expr::Set { .. } => expr_visitor.visit_set_expression(self),
-            Expr::Super { .. } => expr_visitor.visit_super_expression(self),
-            Expr::This { .. } => expr_visitor.visit_this_expression(self),
-            Expr::Unary { .. } => expr_visitor.visit_unary_expression(self),
-            Expr::Variable { .. } => expr_visitor.visit_variable_expression(self),
+    // Structural equality that ignores spans/lines, so a tree parsed from
+    // one source string can be compared against a tree parsed from a
+    // different (but equivalent) one, e.g. in the precedence round-trip test.
+    pub fn eq_ignore_span(&self, other: &Expr) -> bool {
+        match (self, other) {
+            (Expr::Assign { name, value, .. }, Expr::Assign { name: n2, value: v2, .. }) => {
+                name.eq_ignore_span(n2) && value.eq_ignore_span(v2)
+            }
+            (Expr::LiteralExpr { value, .. }, Expr::LiteralExpr { value: v2, .. }) => value == v2,
+            (Expr::Binary { left, operator, right, .. }, Expr::Binary { left: l2, operator: o2, right: r2, .. }) => {
+                left.eq_ignore_span(l2) && operator.eq_ignore_span(o2) && right.eq_ignore_span(r2)
+            }
+            (Expr::Call { callee, paren, arguments, .. }, Expr::Call { callee: c2, paren: p2, arguments: a2, .. }) => {
+                callee.eq_ignore_span(c2)
+                    && paren.eq_ignore_span(p2)
+                    && arguments.len() == a2.len()
+                    && arguments.iter().zip(a2).all(|(a, b)| a.eq_ignore_span(b))
+            }
+            (Expr::Get { object, name, .. }, Expr::Get { object: o2, name: n2, .. }) => {
+                object.eq_ignore_span(o2) && name.eq_ignore_span(n2)
+            }
+            (Expr::Grouping { expression, .. }, Expr::Grouping { expression: e2, .. }) => expression.eq_ignore_span(e2),
+            (Expr::Logical { left, operator, right, .. }, Expr::Logical { left: l2, operator: o2, right: r2, .. }) => {
+                left.eq_ignore_span(l2) && operator.eq_ignore_span(o2) && right.eq_ignore_span(r2)
+            }
+            (Expr::Set { object, name, value, .. }, Expr::Set { object: o2, name: n2, value: v2, .. }) => {
+                object.eq_ignore_span(o2) && name.eq_ignore_span(n2) && value.eq_ignore_span(v2)
+            }
+            (Expr::Super { keyword, method, .. }, Expr::Super { keyword: k2, method: m2, .. }) => {
+                keyword.eq_ignore_span(k2) && method.eq_ignore_span(m2)
+            }
+            (Expr::This { keyword, .. }, Expr::This { keyword: k2, .. }) => keyword.eq_ignore_span(k2),
+            (Expr::Unary { operator, right, .. }, Expr::Unary { operator: o2, right: r2, .. }) => {
+                operator.eq_ignore_span(o2) && right.eq_ignore_span(r2)
+            }
+            (Expr::Variable { name, .. }, Expr::Variable { name: n2, .. }) => name.eq_ignore_span(n2),
+            _ => false,
         }
     }
 }
-
-pub trait Visitor<R> {
-    fn visit_binary_expression(&self, expr: &Expr) -> R;
-    fn visit_literal_expression(&self, expr: &Expr) -> R;
-    fn visit_assign_expression(&self, expr: &Expr) -> R;
-    fn visit_call_expression(&self, expr: &Expr) -> R;
-    fn visit_get_expression(&self, expr: &Expr) -> R;
-    fn visit_grouping_expression(&self, expr: &Expr) -> R;
-    fn visit_logical_expression(&self, expr: &Expr) -> R;
-    fn visit_set_expression(&self, expr: &Expr) -> R;
-    fn visit_super_expression(&self, expr: &Expr) -> R;
-    fn visit_this_expression(&self, expr: &Expr) -> R;
-    fn visit_unary_expression(&self, expr: &Expr) -> R;
-    fn visit_variable_expression(&self, expr: &Expr) -> R;
-}
-
@@ -0,0 +1,605 @@
+use crate::diagnostics::Diagnostic;
+use crate::expressions::{Expr, Visitor};
+use crate::lexer::{Literal, TokenType};
+use crate::statement::{Stmt, StmtVisitor};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+// A Lox function or class value, closed over the environment it was
+// declared in.
+#[derive(Clone)]
+pub enum Callable {
+    Function { declaration: Rc<Stmt>, closure: Rc<RefCell<Environment>> },
+    Class { name: String, methods: Rc<HashMap<String, Callable>> },
+}
+
+impl Callable {
+    fn name(&self) -> &str {
+        match self {
+            Callable::Function { declaration, .. } => match declaration.as_ref() {
+                Stmt::Function { name, .. } => name.lexeme.as_str(),
+                _ => unreachable!("Callable::Function always wraps a Stmt::Function"),
+            },
+            Callable::Class { name, .. } => name.as_str(),
+        }
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            Callable::Function { declaration, .. } => match declaration.as_ref() {
+                Stmt::Function { params, .. } => params.len(),
+                _ => unreachable!("Callable::Function always wraps a Stmt::Function"),
+            },
+            Callable::Class { .. } => 0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Instance {
+    class_name: String,
+    methods: Rc<HashMap<String, Callable>>,
+    fields: Rc<RefCell<HashMap<String, Value>>>,
+}
+
+#[derive(Clone)]
+pub enum Value {
+    Number(f32),
+    String(String),
+    Bool(bool),
+    Nil,
+    Callable(Callable),
+    Instance(Instance),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(n) if n.fract() == 0.0 => write!(f, "{}", *n as i64),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Nil => write!(f, "nil"),
+            Value::Callable(c) => write!(f, "<fn {}>", c.name()),
+            Value::Instance(i) => write!(f, "{} instance", i.class_name),
+        }
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Nil | Value::Bool(false))
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x == y,
+        (Value::String(x), Value::String(y)) => x == y,
+        (Value::Bool(x), Value::Bool(y)) => x == y,
+        (Value::Nil, Value::Nil) => true,
+        _ => false,
+    }
+}
+
+// A runtime failure, or the early-exit used to unwind a `return` statement
+// back to the call boundary that invoked the enclosing function.
+pub enum RuntimeError {
+    Error { message: String, span: (usize, usize) },
+    Return { value: Value },
+}
+
+impl RuntimeError {
+    fn error(message: impl Into<String>, span: (usize, usize)) -> RuntimeError {
+        RuntimeError::Error { message: message.into(), span }
+    }
+
+    pub fn diagnostic(&self) -> Option<Diagnostic> {
+        match self {
+            RuntimeError::Error { message, span } => Some(Diagnostic::error(message.clone(), *span)),
+            RuntimeError::Return { .. } => None,
+        }
+    }
+}
+
+// Nested lexical scopes, each a flat map with a pointer to its enclosing
+// scope so lookups and assignments walk outward until they find a binding.
+pub struct Environment {
+    values: HashMap<String, Value>,
+    parent: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Environment {
+    pub fn new() -> Environment {
+        Environment { values: HashMap::new(), parent: None }
+    }
+
+    pub fn with_parent(parent: Rc<RefCell<Environment>>) -> Environment {
+        Environment { values: HashMap::new(), parent: Some(parent) }
+    }
+
+    pub fn define(&mut self, name: String, value: Value) {
+        self.values.insert(name, value);
+    }
+
+    pub fn get(&self, name: &str, span: (usize, usize)) -> Result<Value, RuntimeError> {
+        if let Some(value) = self.values.get(name) {
+            return Ok(value.clone());
+        }
+        if let Some(parent) = &self.parent {
+            return parent.borrow().get(name, span);
+        }
+        Err(RuntimeError::error(format!("Undefined variable '{}'.", name), span))
+    }
+
+    pub fn assign(&mut self, name: &str, value: Value, span: (usize, usize)) -> Result<(), RuntimeError> {
+        if self.values.contains_key(name) {
+            self.values.insert(name.to_string(), value);
+            return Ok(());
+        }
+        if let Some(parent) = &self.parent {
+            return parent.borrow_mut().assign(name, value, span);
+        }
+        Err(RuntimeError::error(format!("Undefined variable '{}'.", name), span))
+    }
+}
+
+// Tree-walking evaluator. Implements Visitor<Result<Value, RuntimeError>> for
+// expressions and StmtVisitor<Result<(), RuntimeError>> for statements; both traits take `&self`, so all mutable
+// state (the current scope) lives behind a RefCell.
+pub struct Interpreter {
+    environment: RefCell<Rc<RefCell<Environment>>>,
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter { environment: RefCell::new(Rc::new(RefCell::new(Environment::new()))) }
+    }
+
+    pub fn interpret(&self, statements: &[Stmt]) -> Result<(), RuntimeError> {
+        for stmt in statements {
+            self.execute(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn execute(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        stmt.accept(self)
+    }
+
+    fn evaluate(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        expr.accept(self)
+    }
+
+    fn current_env(&self) -> Rc<RefCell<Environment>> {
+        self.environment.borrow().clone()
+    }
+
+    fn execute_block(&self, statements: &[Stmt], env: Rc<RefCell<Environment>>) -> Result<(), RuntimeError> {
+        let previous = self.current_env();
+        *self.environment.borrow_mut() = env;
+
+        let result = statements.iter().try_for_each(|stmt| self.execute(stmt));
+
+        *self.environment.borrow_mut() = previous;
+        result
+    }
+
+    fn call(&self, callee: Value, arguments: Vec<Value>, span: (usize, usize)) -> Result<Value, RuntimeError> {
+        let callable = match callee {
+            Value::Callable(callable) => callable,
+            _ => return Err(RuntimeError::error("Can only call functions and classes.", span)),
+        };
+
+        if arguments.len() != callable.arity() {
+            return Err(RuntimeError::error(
+                format!("Expected {} arguments but got {}.", callable.arity(), arguments.len()),
+                span,
+            ));
+        }
+
+        match callable {
+            Callable::Function { declaration, closure } => {
+                let (params, body) = match declaration.as_ref() {
+                    Stmt::Function { params, body, .. } => (params, body),
+                    _ => unreachable!("Callable::Function always wraps a Stmt::Function"),
+                };
+
+                let env = Rc::new(RefCell::new(Environment::with_parent(closure)));
+                for (param, argument) in params.iter().zip(arguments) {
+                    env.borrow_mut().define(param.lexeme.clone(), argument);
+                }
+
+                match self.execute_block(body, env) {
+                    Ok(()) => Ok(Value::Nil),
+                    Err(RuntimeError::Return { value }) => Ok(value),
+                    Err(error) => Err(error),
+                }
+            }
+            Callable::Class { name, methods } => {
+                Ok(Value::Instance(Instance { class_name: name, methods, fields: Rc::new(RefCell::new(HashMap::new())) }))
+            }
+        }
+    }
+
+    fn bind(&self, method: &Callable, instance: &Instance) -> Callable {
+        match method {
+            Callable::Function { declaration, closure } => {
+                let env = Rc::new(RefCell::new(Environment::with_parent(closure.clone())));
+                env.borrow_mut().define("this".to_string(), Value::Instance(instance.clone()));
+                Callable::Function { declaration: declaration.clone(), closure: env }
+            }
+            Callable::Class { .. } => method.clone(),
+        }
+    }
+}
+
+impl Visitor<Result<Value, RuntimeError>> for Interpreter {
+    fn visit_binary_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Binary { left, operator, right, .. } = expr else { unreachable!() };
+        let left = self.evaluate(left)?;
+        let right = self.evaluate(right)?;
+
+        match (operator.token_type, &left, &right) {
+            (TokenType::PLUS, Value::Number(a), Value::Number(b)) => Ok(Value::Number(a + b)),
+            (TokenType::PLUS, Value::String(a), Value::String(b)) => Ok(Value::String(format!("{}{}", a, b))),
+            (TokenType::PLUS, _, _) => Err(RuntimeError::error("Operands must be two numbers or two strings.", operator.span)),
+            (TokenType::MINUS, Value::Number(a), Value::Number(b)) => Ok(Value::Number(a - b)),
+            (TokenType::SLASH, Value::Number(a), Value::Number(b)) => Ok(Value::Number(a / b)),
+            (TokenType::STAR, Value::Number(a), Value::Number(b)) => Ok(Value::Number(a * b)),
+            (TokenType::GREATER, Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a > b)),
+            (TokenType::GREATER_EQUAL, Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a >= b)),
+            (TokenType::LESS, Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a < b)),
+            (TokenType::LESS_EQUAL, Value::Number(a), Value::Number(b)) => Ok(Value::Bool(a <= b)),
+            (TokenType::BANG_EQUAL, a, b) => Ok(Value::Bool(!values_equal(a, b))),
+            (TokenType::EQUAL_EQUAL, a, b) => Ok(Value::Bool(values_equal(a, b))),
+            (TokenType::MINUS | TokenType::SLASH | TokenType::STAR | TokenType::GREATER | TokenType::GREATER_EQUAL | TokenType::LESS | TokenType::LESS_EQUAL, _, _) => {
+                Err(RuntimeError::error("Operands must be numbers.", operator.span))
+            }
+            _ => unreachable!("scanner/parser never produce a non-binary operator here"),
+        }
+    }
+
+    fn visit_literal_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::LiteralExpr { value, .. } = expr else { unreachable!() };
+        Ok(match value {
+            Literal::String(s) => Value::String(s.clone()),
+            Literal::Number(n, _) => Value::Number(*n),
+            Literal::Bool(b) => Value::Bool(*b),
+            Literal::Nil => Value::Nil,
+        })
+    }
+
+    fn visit_assign_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Assign { name, value, .. } = expr else { unreachable!() };
+        let value = self.evaluate(value)?;
+        self.current_env().borrow_mut().assign(name.lexeme.as_str(), value.clone(), name.span)?;
+        Ok(value)
+    }
+
+    fn visit_call_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Call { callee, paren, arguments, .. } = expr else { unreachable!() };
+        let callee = self.evaluate(callee)?;
+        let arguments = arguments.iter().map(|arg| self.evaluate(arg)).collect::<Result<Vec<_>, _>>()?;
+        self.call(callee, arguments, paren.span)
+    }
+
+    fn visit_get_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Get { object, name, .. } = expr else { unreachable!() };
+        let object = self.evaluate(object)?;
+
+        match object {
+            Value::Instance(instance) => {
+                if let Some(value) = instance.fields.borrow().get(name.lexeme.as_str()) {
+                    return Ok(value.clone());
+                }
+                if let Some(method) = instance.methods.get(name.lexeme.as_str()) {
+                    return Ok(Value::Callable(self.bind(method, &instance)));
+                }
+                Err(RuntimeError::error(format!("Undefined property '{}'.", name.lexeme), name.span))
+            }
+            _ => Err(RuntimeError::error("Only instances have properties.", name.span)),
+        }
+    }
+
+    fn visit_grouping_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Grouping { expression, .. } = expr else { unreachable!() };
+        self.evaluate(expression)
+    }
+
+    fn visit_logical_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Logical { left, operator, right, .. } = expr else { unreachable!() };
+        let left = self.evaluate(left)?;
+
+        if operator.token_type == TokenType::OR {
+            if is_truthy(&left) {
+                return Ok(left);
+            }
+        } else if !is_truthy(&left) {
+            return Ok(left);
+        }
+
+        self.evaluate(right)
+    }
+
+    fn visit_set_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Set { object, name, value, .. } = expr else { unreachable!() };
+        let object = self.evaluate(object)?;
+
+        match object {
+            Value::Instance(instance) => {
+                let value = self.evaluate(value)?;
+                instance.fields.borrow_mut().insert(name.lexeme.clone(), value.clone());
+                Ok(value)
+            }
+            _ => Err(RuntimeError::error("Only instances have fields.", name.span)),
+        }
+    }
+
+    fn visit_super_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Super { keyword, method, .. } = expr else { unreachable!() };
+        let superclass = self.current_env().borrow().get("super", keyword.span)?;
+        let instance = self.current_env().borrow().get("this", keyword.span)?;
+
+        match (superclass, instance) {
+            (Value::Callable(Callable::Class { methods, .. }), Value::Instance(instance)) => methods
+                .get(method.lexeme.as_str())
+                .map(|m| Value::Callable(self.bind(m, &instance)))
+                .ok_or_else(|| RuntimeError::error(format!("Undefined property '{}'.", method.lexeme), method.span)),
+            _ => unreachable!("'super' only resolves inside a subclass method"),
+        }
+    }
+
+    fn visit_this_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::This { keyword, .. } = expr else { unreachable!() };
+        self.current_env().borrow().get("this", keyword.span)
+    }
+
+    fn visit_unary_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Unary { operator, right, .. } = expr else { unreachable!() };
+        let right = self.evaluate(right)?;
+
+        match operator.token_type {
+            TokenType::MINUS => match right {
+                Value::Number(n) => Ok(Value::Number(-n)),
+                _ => Err(RuntimeError::error("Operand must be a number.", operator.span)),
+            },
+            TokenType::BANG => Ok(Value::Bool(!is_truthy(&right))),
+            _ => unreachable!("scanner/parser never produce a non-unary operator here"),
+        }
+    }
+
+    fn visit_variable_expression(&self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let Expr::Variable { name, .. } = expr else { unreachable!() };
+        self.current_env().borrow().get(name.lexeme.as_str(), name.span)
+    }
+}
+
+impl StmtVisitor<Result<(), RuntimeError>> for Interpreter {
+    fn visit_block_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::Block { statements, .. } = stmt else { unreachable!() };
+        let env = Rc::new(RefCell::new(Environment::with_parent(self.current_env())));
+        self.execute_block(statements, env)
+    }
+
+    fn visit_class_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::Class { name, super_class, methods, .. } = stmt else { unreachable!() };
+
+        let superclass = match super_class {
+            Expr::LiteralExpr { value: Literal::Nil, .. } => None,
+            other => match self.evaluate(other)? {
+                value @ Value::Callable(Callable::Class { .. }) => Some(value),
+                _ => return Err(RuntimeError::error("Superclass must be a class.", other.span())),
+            },
+        };
+
+        self.current_env().borrow_mut().define(name.lexeme.clone(), Value::Nil);
+
+        let method_closure = match &superclass {
+            Some(Value::Callable(Callable::Class { .. })) => {
+                let env = Rc::new(RefCell::new(Environment::with_parent(self.current_env())));
+                env.borrow_mut().define("super".to_string(), superclass.clone().unwrap());
+                env
+            }
+            _ => self.current_env(),
+        };
+
+        let mut method_map = match &superclass {
+            Some(Value::Callable(Callable::Class { methods, .. })) => (**methods).clone(),
+            _ => HashMap::new(),
+        };
+        for method in methods {
+            if let Stmt::Function { name: method_name, .. } = method {
+                method_map.insert(
+                    method_name.lexeme.clone(),
+                    Callable::Function { declaration: Rc::new(method.clone()), closure: method_closure.clone() },
+                );
+            }
+        }
+
+        let class = Value::Callable(Callable::Class { name: name.lexeme.clone(), methods: Rc::new(method_map) });
+        self.current_env().borrow_mut().assign(name.lexeme.as_str(), class, name.span)?;
+        Ok(())
+    }
+
+    fn visit_expression_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::Expression { expression, .. } = stmt else { unreachable!() };
+        self.evaluate(expression)?;
+        Ok(())
+    }
+
+    fn visit_function_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::Function { name, .. } = stmt else { unreachable!() };
+        let callable = Callable::Function { declaration: Rc::new(stmt.clone()), closure: self.current_env() };
+        self.current_env().borrow_mut().define(name.lexeme.clone(), Value::Callable(callable));
+        Ok(())
+    }
+
+    fn visit_if_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::If { condition, then_branch, else_branch, .. } = stmt else { unreachable!() };
+        if is_truthy(&self.evaluate(condition)?) {
+            self.execute(then_branch)
+        } else {
+            self.execute(else_branch)
+        }
+    }
+
+    fn visit_print_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::Print { expression, .. } = stmt else { unreachable!() };
+        let value = self.evaluate(expression)?;
+        println!("{}", value);
+        Ok(())
+    }
+
+    fn visit_return_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::Return { value, .. } = stmt else { unreachable!() };
+        Err(RuntimeError::Return { value: self.evaluate(value)? })
+    }
+
+    fn visit_variable_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::Variable { name, initializer, .. } = stmt else { unreachable!() };
+        let value = self.evaluate(initializer)?;
+        self.current_env().borrow_mut().define(name.lexeme.clone(), value);
+        Ok(())
+    }
+
+    fn visit_while_stmt(&self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        let Stmt::While { condition, body, .. } = stmt else { unreachable!() };
+        while is_truthy(&self.evaluate(condition)?) {
+            self.execute(body)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Scanner;
+    use crate::parser::Parser;
+
+    // Scans, parses, and interprets `source`, returning the interpreter so
+    // tests can inspect variables it defined at the top level.
+    fn run(source: &str) -> Interpreter {
+        let tokens = Scanner::new(source.to_string()).scan_tokens();
+        let statements = Parser::new(tokens).parse().expect("test source must parse");
+        let interpreter = Interpreter::new();
+        if interpreter.interpret(&statements).is_err() {
+            panic!("test source must not raise a runtime error");
+        }
+        interpreter
+    }
+
+    fn var(interpreter: &Interpreter, name: &str) -> Value {
+        match interpreter.current_env().borrow().get(name, (0, 0)) {
+            Ok(value) => value,
+            Err(_) => panic!("variable must be defined"),
+        }
+    }
+
+    fn assert_number(value: &Value, expected: f32) {
+        match value {
+            Value::Number(n) => assert_eq!(*n, expected),
+            _ => panic!("expected a number"),
+        }
+    }
+
+    fn assert_string(value: &Value, expected: &str) {
+        match value {
+            Value::String(s) => assert_eq!(s, expected),
+            _ => panic!("expected a string"),
+        }
+    }
+
+    fn assert_bool(value: &Value, expected: bool) {
+        match value {
+            Value::Bool(b) => assert_eq!(*b, expected),
+            _ => panic!("expected a bool"),
+        }
+    }
+
+    #[test]
+    fn arithmetic_and_string_plus_overloading() {
+        let interpreter = run(r#"var a = 1 + 2 * 3; var b = "foo" + "bar";"#);
+        assert_number(&var(&interpreter, "a"), 7.0);
+        assert_string(&var(&interpreter, "b"), "foobar");
+    }
+
+    #[test]
+    fn plus_rejects_mixed_number_and_string_operands() {
+        let tokens = Scanner::new("1 + \"a\";".to_string()).scan_tokens();
+        let statements = Parser::new(tokens).parse().expect("test source must parse");
+        let error = match Interpreter::new().interpret(&statements) {
+            Err(error) => error,
+            Ok(()) => panic!("mixed + should raise a runtime error"),
+        };
+        match error {
+            RuntimeError::Error { message, .. } => assert_eq!(message, "Operands must be two numbers or two strings."),
+            RuntimeError::Return { .. } => panic!("expected a runtime error, not a return unwind"),
+        }
+    }
+
+    #[test]
+    fn truthiness_and_short_circuit_logical_operators() {
+        let interpreter = run("var a = nil or 5; var b = false and 10; var c = 1 and 2;");
+        assert_number(&var(&interpreter, "a"), 5.0);
+        assert_bool(&var(&interpreter, "b"), false);
+        assert_number(&var(&interpreter, "c"), 2.0);
+    }
+
+    #[test]
+    fn closures_capture_their_defining_environment() {
+        let interpreter = run(
+            r#"
+            fun make_counter() {
+                var i = 0;
+                fun counter() {
+                    i = i + 1;
+                    return i;
+                }
+                return counter;
+            }
+            var counter = make_counter();
+            var first = counter();
+            var second = counter();
+            "#,
+        );
+        assert_number(&var(&interpreter, "first"), 1.0);
+        assert_number(&var(&interpreter, "second"), 2.0);
+    }
+
+    #[test]
+    fn calling_a_function_with_the_wrong_arity_raises_a_runtime_error() {
+        let tokens = Scanner::new("fun add(a, b) { return a + b; } add(1);".to_string()).scan_tokens();
+        let statements = Parser::new(tokens).parse().expect("test source must parse");
+        let error = match Interpreter::new().interpret(&statements) {
+            Err(error) => error,
+            Ok(()) => panic!("wrong arity should raise a runtime error"),
+        };
+        match error {
+            RuntimeError::Error { message, .. } => assert_eq!(message, "Expected 2 arguments but got 1."),
+            RuntimeError::Return { .. } => panic!("expected a runtime error, not a return unwind"),
+        }
+    }
+
+    #[test]
+    fn classes_inherit_methods_and_super_reaches_the_parent_implementation() {
+        let interpreter = run(
+            r#"
+            class Base {
+                greet() {
+                    return "base";
+                }
+            }
+            class Derived < Base {
+                greet() {
+                    return super.greet() + "-derived";
+                }
+            }
+            var d = Derived();
+            var result = d.greet();
+            "#,
+        );
+        assert_string(&var(&interpreter, "result"), "base-derived");
+    }
+}
@@ -0,0 +1,324 @@
+use crate::diagnostics::{Diagnostic, DiagnosticSink};
+use crate::utils::{is_alpha_numeric, is_digit};
+use std::fmt;
+
+#[allow(non_camel_case_types)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    LEFT_PAREN,
+    RIGHT_PAREN,
+    LEFT_BRACE,
+    RIGHT_BRACE,
+    COMMA,
+    DOT,
+    MINUS,
+    PLUS,
+    SEMICOLON,
+    SLASH,
+    STAR,
+    BANG,
+    BANG_EQUAL,
+    EQUAL,
+    EQUAL_EQUAL,
+    GREATER,
+    GREATER_EQUAL,
+    LESS,
+    LESS_EQUAL,
+    IDENTIFIER,
+    STRING,
+    NUMBER,
+    AND,
+    CLASS,
+    ELSE,
+    FALSE,
+    FUN,
+    FOR,
+    IF,
+    NIL,
+    OR,
+    PRINT,
+    RETURN,
+    SUPER,
+    THIS,
+    TRUE,
+    VAR,
+    WHILE,
+    EOF,
+}
+
+impl fmt::Display for TokenType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    String(String),
+    Number(f32, usize),
+    Bool(bool),
+    Nil,
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Literal::String(value) => write!(f, "{}", value),
+            Literal::Number(value, precision) => write!(f, "{:.*}", precision, value),
+            Literal::Bool(value) => write!(f, "{}", value),
+            Literal::Nil => write!(f, "nil"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub token_type: TokenType,
+    pub lexeme: String,
+    pub literal: Literal,
+    pub line: usize,
+    pub span: (usize, usize),
+}
+
+impl Token {
+    pub fn new(token_type: TokenType, lexeme: &str, literal: Literal, line: usize, span: (usize, usize)) -> Token {
+        Token { token_type, lexeme: String::from(lexeme), literal, line, span }
+    }
+
+    // Compares type and lexeme only, ignoring span/line/literal — used by
+    // `Expr`/`Stmt::eq_ignore_span` to compare ASTs built from different
+    // source strings (e.g. a parse vs. a re-parenthesized re-parse).
+    pub fn eq_ignore_span(&self, other: &Token) -> bool {
+        self.token_type == other.token_type && self.lexeme == other.lexeme
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.token_type, self.lexeme, self.literal)
+    }
+}
+
+fn keyword(text: &str) -> Option<TokenType> {
+    match text {
+        "and" => Some(TokenType::AND),
+        "class" => Some(TokenType::CLASS),
+        "else" => Some(TokenType::ELSE),
+        "false" => Some(TokenType::FALSE),
+        "for" => Some(TokenType::FOR),
+        "fun" => Some(TokenType::FUN),
+        "if" => Some(TokenType::IF),
+        "nil" => Some(TokenType::NIL),
+        "or" => Some(TokenType::OR),
+        "print" => Some(TokenType::PRINT),
+        "return" => Some(TokenType::RETURN),
+        "super" => Some(TokenType::SUPER),
+        "this" => Some(TokenType::THIS),
+        "true" => Some(TokenType::TRUE),
+        "var" => Some(TokenType::VAR),
+        "while" => Some(TokenType::WHILE),
+        _ => None,
+    }
+}
+
+pub struct Scanner {
+    source: Vec<char>,
+    // Byte offset of each char in `source` within the original string, plus
+    // one trailing entry for the source's total byte length. `start`/
+    // `current` index into `source` (i.e. count chars), so spans handed out
+    // to `Token`/`Diagnostic` go through this table to become real byte
+    // offsets — otherwise multi-byte UTF-8 chars would desync the caret
+    // rendering in `diagnostics::render` from the source it slices.
+    byte_offsets: Vec<usize>,
+    tokens: Vec<Token>,
+    start: usize,
+    current: usize,
+    line: usize,
+    diagnostics: DiagnosticSink,
+}
+
+impl Scanner {
+    pub fn new(source: String) -> Scanner {
+        let mut byte_offsets: Vec<usize> = source.char_indices().map(|(offset, _)| offset).collect();
+        byte_offsets.push(source.len());
+
+        Scanner {
+            source: source.chars().collect(),
+            byte_offsets,
+            tokens: Vec::new(),
+            start: 0,
+            current: 0,
+            line: 1,
+            diagnostics: DiagnosticSink::new(),
+        }
+    }
+
+    // Translates a `[start, end)` char-index range (as tracked by `start`/
+    // `current`) into the matching byte-offset range in the original source.
+    fn byte_span(&self, start: usize, end: usize) -> (usize, usize) {
+        (self.byte_offsets[start], self.byte_offsets[end])
+    }
+
+    pub fn diagnostics(&self) -> &DiagnosticSink {
+        &self.diagnostics
+    }
+
+    pub fn scan_tokens(&mut self) -> Vec<Token> {
+        while !self.is_at_end() {
+            self.start = self.current;
+            self.scan_token();
+        }
+
+        let eof_span = self.byte_span(self.current, self.current);
+        self.tokens.push(Token::new(TokenType::EOF, "", Literal::Nil, self.line, eof_span));
+
+        self.tokens.clone()
+    }
+
+    fn scan_token(&mut self) {
+        let c = self.advance();
+
+        match c {
+            '(' => self.add_token(TokenType::LEFT_PAREN),
+            ')' => self.add_token(TokenType::RIGHT_PAREN),
+            '{' => self.add_token(TokenType::LEFT_BRACE),
+            '}' => self.add_token(TokenType::RIGHT_BRACE),
+            ',' => self.add_token(TokenType::COMMA),
+            '.' => self.add_token(TokenType::DOT),
+            '-' => self.add_token(TokenType::MINUS),
+            '+' => self.add_token(TokenType::PLUS),
+            ';' => self.add_token(TokenType::SEMICOLON),
+            '*' => self.add_token(TokenType::STAR),
+            '!' => {
+                let token_type = if self.matches('=') { TokenType::BANG_EQUAL } else { TokenType::BANG };
+                self.add_token(token_type);
+            }
+            '=' => {
+                let token_type = if self.matches('=') { TokenType::EQUAL_EQUAL } else { TokenType::EQUAL };
+                self.add_token(token_type);
+            }
+            '<' => {
+                let token_type = if self.matches('=') { TokenType::LESS_EQUAL } else { TokenType::LESS };
+                self.add_token(token_type);
+            }
+            '>' => {
+                let token_type = if self.matches('=') { TokenType::GREATER_EQUAL } else { TokenType::GREATER };
+                self.add_token(token_type);
+            }
+            '/' => {
+                if self.matches('/') {
+                    while self.peek() != '\n' && !self.is_at_end() {
+                        self.advance();
+                    }
+                } else {
+                    self.add_token(TokenType::SLASH);
+                }
+            }
+            ' ' | '\r' | '\t' => {}
+            '\n' => self.line += 1,
+            '"' => self.string(),
+            c if is_digit(c) => self.number(),
+            c if c.is_alphabetic() || c == '_' => self.identifier(),
+            c => self.error(format!("Unexpected character: {}", c)),
+        }
+    }
+
+    fn identifier(&mut self) {
+        while is_alpha_numeric(self.peek()) {
+            self.advance();
+        }
+
+        let text: String = self.source[self.start..self.current].iter().collect();
+        let token_type = keyword(text.as_str()).unwrap_or(TokenType::IDENTIFIER);
+        self.add_token(token_type);
+    }
+
+    fn number(&mut self) {
+        while is_digit(self.peek()) {
+            self.advance();
+        }
+
+        let mut precision = 0;
+        if self.peek() == '.' && is_digit(self.peek_next()) {
+            self.advance();
+
+            while is_digit(self.peek()) {
+                self.advance();
+                precision += 1;
+            }
+        }
+
+        let text: String = self.source[self.start..self.current].iter().collect();
+        let value: f32 = text.parse().unwrap();
+        self.add_token_with_literal(TokenType::NUMBER, Literal::Number(value, precision));
+    }
+
+    fn string(&mut self) {
+        while self.peek() != '"' && !self.is_at_end() {
+            if self.peek() == '\n' {
+                self.line += 1;
+            }
+            self.advance();
+        }
+
+        if self.is_at_end() {
+            self.error(String::from("Unterminated string."));
+            return;
+        }
+
+        // Consume the closing quote.
+        self.advance();
+
+        let value: String = self.source[self.start + 1..self.current - 1].iter().collect();
+        self.add_token_with_literal(TokenType::STRING, Literal::String(value));
+    }
+
+    fn advance(&mut self) -> char {
+        let c = self.source[self.current];
+        self.current += 1;
+        c
+    }
+
+    fn matches(&mut self, expected: char) -> bool {
+        if self.is_at_end() || self.source[self.current] != expected {
+            return false;
+        }
+
+        self.current += 1;
+        true
+    }
+
+    fn peek(&self) -> char {
+        if self.is_at_end() {
+            return '\0';
+        }
+        self.source[self.current]
+    }
+
+    fn peek_next(&self) -> char {
+        if self.current + 1 >= self.source.len() {
+            return '\0';
+        }
+        self.source[self.current + 1]
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.current >= self.source.len()
+    }
+
+    fn add_token(&mut self, token_type: TokenType) {
+        self.add_token_with_literal(token_type, Literal::Nil);
+    }
+
+    fn add_token_with_literal(&mut self, token_type: TokenType, literal: Literal) {
+        let lexeme: String = self.source[self.start..self.current].iter().collect();
+        let span = self.byte_span(self.start, self.current);
+        self.tokens.push(Token::new(token_type, lexeme.as_str(), literal, self.line, span));
+    }
+
+    fn error(&mut self, message: String) {
+        let span = self.byte_span(self.start, self.current);
+        self.diagnostics.push(Diagnostic::error(message, span));
+    }
+}
@@ -3,16 +3,63 @@ mod utils;
 mod expressions;
 mod statement;
 mod ast;
+mod diagnostics;
+mod parser;
+mod interpreter;
 
+use statement::Stmt;
 use std::env;
 use std::fs;
 use std::io::{self, Write};
+use std::process;
+
+fn read_source(filename: &str) -> String {
+    let file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
+        writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
+        String::new()
+    });
+    // Fuck windows
+    file_contents.replace("\r\n", "\n")
+}
+
+// `Scanner::scan_tokens` only scans — it never prints — so every command
+// that wants the codecrafters-style token dump (currently just `tokenize`)
+// must call this explicitly.
+fn print_tokens(tokens: &[lexer::Token]) {
+    for token in tokens {
+        println!("{}", token);
+    }
+}
+
+// Scans and parses `filename`, printing any diagnostics and exiting with
+// status 65 (the codecrafters/lox convention for a syntax error) if either
+// stage fails.
+fn scan_and_parse(filename: &str) -> (String, Vec<Stmt>) {
+    let file_contents = read_source(filename);
+    let mut scanner = lexer::Scanner::new(file_contents.clone());
+    let tokens = scanner.scan_tokens();
+
+    if scanner.diagnostics().has_errors() {
+        scanner.diagnostics().report(&file_contents);
+        process::exit(65);
+    }
+
+    match parser::Parser::new(tokens).parse() {
+        Ok(statements) => (file_contents, statements),
+        Err(parse_errors) => {
+            for diagnostic in &parse_errors {
+                eprint!("{}", diagnostics::render(&file_contents, diagnostic));
+            }
+            process::exit(65);
+        }
+    }
+}
 
 // TODO: After implementing the lexer, create unit tests for each operation to make that all cases are being covered
 fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() < 3 {
-        writeln!(io::stderr(), "Usage: {} tokenize <filename>", args[0]).unwrap();
+        writeln!(io::stderr(), "Usage: {} tokenize|parse|run|dump-ast <filename>", args[0]).unwrap();
         return;
     }
 
@@ -23,13 +70,32 @@ fn main() {
         "tokenize" => {
             writeln!(io::stderr(), "Logs from your program will appear here!").unwrap();
 
-            let mut file_contents = fs::read_to_string(filename).unwrap_or_else(|_| {
-                writeln!(io::stderr(), "Failed to read file {}", filename).unwrap();
-                String::new()
-            });
-            // Fuck windows
-            file_contents = file_contents.replace("\r\n", "\n");
-            lexer::Scanner::new(file_contents).scan_tokens();
+            let file_contents = read_source(filename);
+            let mut scanner = lexer::Scanner::new(file_contents.clone());
+            let tokens = scanner.scan_tokens();
+            print_tokens(&tokens);
+
+            if scanner.diagnostics().has_errors() {
+                scanner.diagnostics().report(&file_contents);
+                process::exit(65);
+            }
+        }
+        "parse" => {
+            scan_and_parse(filename);
+        }
+        "dump-ast" => {
+            let (_, statements) = scan_and_parse(filename);
+            println!("{}", ast::AstPrinter {}.print_program(&statements));
+        }
+        "run" => {
+            let (file_contents, statements) = scan_and_parse(filename);
+
+            if let Err(error) = interpreter::Interpreter::new().interpret(&statements) {
+                if let Some(diagnostic) = error.diagnostic() {
+                    eprint!("{}", diagnostics::render(&file_contents, &diagnostic));
+                }
+                process::exit(70);
+            }
         }
         _ => {
             writeln!(io::stderr(), "Unknown command: {}", command).unwrap();
@@ -0,0 +1,608 @@
+use crate::diagnostics::Diagnostic;
+use crate::expressions::Expr;
+use crate::lexer::{Literal, Token, TokenType};
+use crate::statement::Stmt;
+
+const STATEMENT_BOUNDARIES: [TokenType; 8] = [
+    TokenType::CLASS,
+    TokenType::FUN,
+    TokenType::VAR,
+    TokenType::FOR,
+    TokenType::IF,
+    TokenType::WHILE,
+    TokenType::PRINT,
+    TokenType::RETURN,
+];
+
+// Recursive-descent parser turning a token stream into an AST. On a parse
+// error it records a Diagnostic and enters panic-mode recovery (see
+// `synchronize`) so one malformed statement doesn't abort the whole file.
+pub struct Parser {
+    tokens: Vec<Token>,
+    current: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Parser {
+    pub fn new(tokens: Vec<Token>) -> Parser {
+        Parser { tokens, current: 0, diagnostics: Vec::new() }
+    }
+
+    pub fn parse(&mut self) -> Result<Vec<Stmt>, Vec<Diagnostic>> {
+        let mut statements = Vec::new();
+
+        while !self.is_at_end() {
+            if let Some(stmt) = self.declaration() {
+                statements.push(stmt);
+            }
+        }
+
+        if self.diagnostics.is_empty() {
+            Ok(statements)
+        } else {
+            Err(self.diagnostics.clone())
+        }
+    }
+
+    fn declaration(&mut self) -> Option<Stmt> {
+        let result = if self.match_token(&[TokenType::CLASS]) {
+            self.class_declaration()
+        } else if self.match_token(&[TokenType::FUN]) {
+            self.function("function")
+        } else if self.match_token(&[TokenType::VAR]) {
+            self.var_declaration()
+        } else {
+            self.statement()
+        };
+
+        match result {
+            Ok(stmt) => Some(stmt),
+            Err(diagnostic) => {
+                self.diagnostics.push(diagnostic);
+                self.synchronize();
+                None
+            }
+        }
+    }
+
+    fn class_declaration(&mut self) -> Result<Stmt, Diagnostic> {
+        let class_keyword = self.previous();
+        let name = self.consume(TokenType::IDENTIFIER, "Expect class name.")?;
+
+        let super_class = if self.match_token(&[TokenType::LESS]) {
+            let super_name = self.consume(TokenType::IDENTIFIER, "Expect superclass name.")?;
+            Expr::Variable { span: super_name.span, name: super_name }
+        } else {
+            self.empty_expr(name.span.1)
+        };
+
+        self.consume(TokenType::LEFT_BRACE, "Expect '{' before class body.")?;
+
+        let mut methods = Vec::new();
+        while !self.check(TokenType::RIGHT_BRACE) && !self.is_at_end() {
+            methods.push(self.function("method")?);
+        }
+
+        let closing_brace = self.consume(TokenType::RIGHT_BRACE, "Expect '}' after class body.")?;
+
+        Ok(Stmt::Class { name, super_class, methods, span: (class_keyword.span.0, closing_brace.span.1) })
+    }
+
+    fn function(&mut self, kind: &str) -> Result<Stmt, Diagnostic> {
+        let name = self.consume(TokenType::IDENTIFIER, &format!("Expect {} name.", kind))?;
+
+        self.consume(TokenType::LEFT_PAREN, &format!("Expect '(' after {} name.", kind))?;
+        let mut params = Vec::new();
+        if !self.check(TokenType::RIGHT_PAREN) {
+            loop {
+                params.push(self.consume(TokenType::IDENTIFIER, "Expect parameter name.")?);
+                if !self.match_token(&[TokenType::COMMA]) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenType::RIGHT_PAREN, "Expect ')' after parameters.")?;
+
+        self.consume(TokenType::LEFT_BRACE, &format!("Expect '{{' before {} body.", kind))?;
+        let (body, body_span) = self.block()?;
+
+        Ok(Stmt::Function { name: name.clone(), params, body, span: (name.span.0, body_span.1) })
+    }
+
+    fn var_declaration(&mut self) -> Result<Stmt, Diagnostic> {
+        let var_keyword = self.previous();
+        let name = self.consume(TokenType::IDENTIFIER, "Expect variable name.")?;
+
+        let initializer = if self.match_token(&[TokenType::EQUAL]) {
+            self.expression()?
+        } else {
+            self.empty_expr(name.span.1)
+        };
+
+        let semicolon = self.consume(TokenType::SEMICOLON, "Expect ';' after variable declaration.")?;
+        Ok(Stmt::Variable { name, initializer, span: (var_keyword.span.0, semicolon.span.1) })
+    }
+
+    fn statement(&mut self) -> Result<Stmt, Diagnostic> {
+        if self.match_token(&[TokenType::FOR]) {
+            return self.for_statement();
+        }
+        if self.match_token(&[TokenType::IF]) {
+            return self.if_statement();
+        }
+        if self.match_token(&[TokenType::PRINT]) {
+            return self.print_statement();
+        }
+        if self.match_token(&[TokenType::RETURN]) {
+            return self.return_statement();
+        }
+        if self.match_token(&[TokenType::WHILE]) {
+            return self.while_statement();
+        }
+        if self.match_token(&[TokenType::LEFT_BRACE]) {
+            let (statements, span) = self.block()?;
+            return Ok(Stmt::Block { statements, span });
+        }
+
+        self.expression_statement()
+    }
+
+    // Desugars `for (init; cond; incr) body` into a block containing the
+    // initializer followed by a `while` whose body is `body` plus `incr`.
+    fn for_statement(&mut self) -> Result<Stmt, Diagnostic> {
+        let for_keyword = self.previous();
+        self.consume(TokenType::LEFT_PAREN, "Expect '(' after 'for'.")?;
+
+        let initializer = if self.match_token(&[TokenType::SEMICOLON]) {
+            None
+        } else if self.match_token(&[TokenType::VAR]) {
+            Some(self.var_declaration()?)
+        } else {
+            Some(self.expression_statement()?)
+        };
+
+        let condition = if !self.check(TokenType::SEMICOLON) {
+            self.expression()?
+        } else {
+            self.empty_expr(self.peek().span.0)
+        };
+        self.consume(TokenType::SEMICOLON, "Expect ';' after loop condition.")?;
+
+        let increment = if !self.check(TokenType::RIGHT_PAREN) { Some(self.expression()?) } else { None };
+        self.consume(TokenType::RIGHT_PAREN, "Expect ')' after for clauses.")?;
+
+        let mut body = self.statement()?;
+        let body_end = body.span().1;
+
+        if let Some(increment) = increment {
+            let increment_span = increment.span();
+            let span = (body.span().0.min(increment_span.0), body.span().1.max(increment_span.1));
+            body = Stmt::Block {
+                span,
+                statements: vec![body, Stmt::Expression { span: increment_span, expression: increment }],
+            };
+        }
+
+        let condition = if matches!(condition, Expr::LiteralExpr { value: Literal::Nil, .. }) {
+            Expr::LiteralExpr { value: Literal::Bool(true), span: condition.span() }
+        } else {
+            condition
+        };
+
+        body = Stmt::While { span: (for_keyword.span.0, body.span().1), condition, body: Box::new(body) };
+
+        if let Some(initializer) = initializer {
+            body = Stmt::Block { span: (for_keyword.span.0, body_end), statements: vec![initializer, body] };
+        }
+
+        Ok(body)
+    }
+
+    fn if_statement(&mut self) -> Result<Stmt, Diagnostic> {
+        let if_keyword = self.previous();
+        self.consume(TokenType::LEFT_PAREN, "Expect '(' after 'if'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RIGHT_PAREN, "Expect ')' after if condition.")?;
+
+        let then_branch = Box::new(self.statement()?);
+        let mut end = then_branch.span().1;
+
+        let else_branch = if self.match_token(&[TokenType::ELSE]) {
+            let branch = self.statement()?;
+            end = branch.span().1;
+            Box::new(branch)
+        } else {
+            Box::new(self.empty_stmt(end))
+        };
+
+        Ok(Stmt::If { condition, then_branch, else_branch, span: (if_keyword.span.0, end) })
+    }
+
+    fn print_statement(&mut self) -> Result<Stmt, Diagnostic> {
+        let print_keyword = self.previous();
+        let value = self.expression()?;
+        let semicolon = self.consume(TokenType::SEMICOLON, "Expect ';' after value.")?;
+        Ok(Stmt::Print { expression: value, span: (print_keyword.span.0, semicolon.span.1) })
+    }
+
+    fn return_statement(&mut self) -> Result<Stmt, Diagnostic> {
+        let keyword = self.previous();
+        let value = if !self.check(TokenType::SEMICOLON) { self.expression()? } else { self.empty_expr(keyword.span.1) };
+        let semicolon = self.consume(TokenType::SEMICOLON, "Expect ';' after return value.")?;
+        Ok(Stmt::Return { keyword: keyword.clone(), value, span: (keyword.span.0, semicolon.span.1) })
+    }
+
+    fn while_statement(&mut self) -> Result<Stmt, Diagnostic> {
+        let while_keyword = self.previous();
+        self.consume(TokenType::LEFT_PAREN, "Expect '(' after 'while'.")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RIGHT_PAREN, "Expect ')' after condition.")?;
+        let body = Box::new(self.statement()?);
+        Ok(Stmt::While { span: (while_keyword.span.0, body.span().1), condition, body })
+    }
+
+    fn block(&mut self) -> Result<(Vec<Stmt>, (usize, usize)), Diagnostic> {
+        let left_brace = self.previous();
+        let mut statements = Vec::new();
+
+        while !self.check(TokenType::RIGHT_BRACE) && !self.is_at_end() {
+            if let Some(stmt) = self.declaration() {
+                statements.push(stmt);
+            }
+        }
+
+        let right_brace = self.consume(TokenType::RIGHT_BRACE, "Expect '}' after block.")?;
+        Ok((statements, (left_brace.span.0, right_brace.span.1)))
+    }
+
+    fn expression_statement(&mut self) -> Result<Stmt, Diagnostic> {
+        let expression = self.expression()?;
+        let start = expression.span().0;
+        let semicolon = self.consume(TokenType::SEMICOLON, "Expect ';' after expression.")?;
+        Ok(Stmt::Expression { expression, span: (start, semicolon.span.1) })
+    }
+
+    fn expression(&mut self) -> Result<Expr, Diagnostic> {
+        self.assignment()
+    }
+
+    fn assignment(&mut self) -> Result<Expr, Diagnostic> {
+        let expr = self.logic_or()?;
+
+        if self.match_token(&[TokenType::EQUAL]) {
+            let equals = self.previous();
+            let value = self.assignment()?;
+
+            return match expr {
+                Expr::Variable { name, .. } => {
+                    Ok(Expr::Assign { span: (name.span.0, value.span().1), name, value: Box::new(value) })
+                }
+                Expr::Get { object, name, .. } => {
+                    Ok(Expr::Set { span: (object.span().0, value.span().1), object, name, value: Box::new(value) })
+                }
+                _ => Err(self.error(&equals, "Invalid assignment target.")),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn logic_or(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.logic_and()?;
+
+        while self.match_token(&[TokenType::OR]) {
+            let operator = self.previous();
+            let right = self.logic_and()?;
+            expr = Expr::Logical { span: (expr.span().0, right.span().1), left: Box::new(expr), operator, right: Box::new(right) };
+        }
+
+        Ok(expr)
+    }
+
+    fn logic_and(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.equality()?;
+
+        while self.match_token(&[TokenType::AND]) {
+            let operator = self.previous();
+            let right = self.equality()?;
+            expr = Expr::Logical { span: (expr.span().0, right.span().1), left: Box::new(expr), operator, right: Box::new(right) };
+        }
+
+        Ok(expr)
+    }
+
+    fn equality(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.comparison()?;
+
+        while self.match_token(&[TokenType::BANG_EQUAL, TokenType::EQUAL_EQUAL]) {
+            let operator = self.previous();
+            let right = self.comparison()?;
+            expr = Expr::Binary { span: (expr.span().0, right.span().1), left: Box::new(expr), operator, right: Box::new(right) };
+        }
+
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.term()?;
+
+        while self.match_token(&[TokenType::GREATER, TokenType::GREATER_EQUAL, TokenType::LESS, TokenType::LESS_EQUAL]) {
+            let operator = self.previous();
+            let right = self.term()?;
+            expr = Expr::Binary { span: (expr.span().0, right.span().1), left: Box::new(expr), operator, right: Box::new(right) };
+        }
+
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.factor()?;
+
+        while self.match_token(&[TokenType::MINUS, TokenType::PLUS]) {
+            let operator = self.previous();
+            let right = self.factor()?;
+            expr = Expr::Binary { span: (expr.span().0, right.span().1), left: Box::new(expr), operator, right: Box::new(right) };
+        }
+
+        Ok(expr)
+    }
+
+    fn factor(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.unary()?;
+
+        while self.match_token(&[TokenType::SLASH, TokenType::STAR]) {
+            let operator = self.previous();
+            let right = self.unary()?;
+            expr = Expr::Binary { span: (expr.span().0, right.span().1), left: Box::new(expr), operator, right: Box::new(right) };
+        }
+
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Diagnostic> {
+        if self.match_token(&[TokenType::BANG, TokenType::MINUS]) {
+            let operator = self.previous();
+            let right = self.unary()?;
+            return Ok(Expr::Unary { span: (operator.span.0, right.span().1), operator, right: Box::new(right) });
+        }
+
+        self.call()
+    }
+
+    fn call(&mut self) -> Result<Expr, Diagnostic> {
+        let mut expr = self.primary()?;
+
+        loop {
+            if self.match_token(&[TokenType::LEFT_PAREN]) {
+                expr = self.finish_call(expr)?;
+            } else if self.match_token(&[TokenType::DOT]) {
+                let name = self.consume(TokenType::IDENTIFIER, "Expect property name after '.'.")?;
+                expr = Expr::Get { span: (expr.span().0, name.span.1), object: Box::new(expr), name };
+            } else {
+                break;
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> Result<Expr, Diagnostic> {
+        let mut arguments = Vec::new();
+
+        if !self.check(TokenType::RIGHT_PAREN) {
+            loop {
+                arguments.push(Box::new(self.expression()?));
+                if !self.match_token(&[TokenType::COMMA]) {
+                    break;
+                }
+            }
+        }
+
+        let paren = self.consume(TokenType::RIGHT_PAREN, "Expect ')' after arguments.")?;
+        Ok(Expr::Call { span: (callee.span().0, paren.span.1), callee: Box::new(callee), paren, arguments })
+    }
+
+    fn primary(&mut self) -> Result<Expr, Diagnostic> {
+        if self.match_token(&[TokenType::FALSE]) {
+            let token = self.previous();
+            return Ok(Expr::LiteralExpr { value: Literal::Bool(false), span: token.span });
+        }
+        if self.match_token(&[TokenType::TRUE]) {
+            let token = self.previous();
+            return Ok(Expr::LiteralExpr { value: Literal::Bool(true), span: token.span });
+        }
+        if self.match_token(&[TokenType::NIL]) {
+            let token = self.previous();
+            return Ok(Expr::LiteralExpr { value: Literal::Nil, span: token.span });
+        }
+        if self.match_token(&[TokenType::NUMBER, TokenType::STRING]) {
+            let token = self.previous();
+            return Ok(Expr::LiteralExpr { value: token.literal.clone(), span: token.span });
+        }
+        if self.match_token(&[TokenType::SUPER]) {
+            let keyword = self.previous();
+            self.consume(TokenType::DOT, "Expect '.' after 'super'.")?;
+            let method = self.consume(TokenType::IDENTIFIER, "Expect superclass method name.")?;
+            return Ok(Expr::Super { span: (keyword.span.0, method.span.1), keyword, method });
+        }
+        if self.match_token(&[TokenType::THIS]) {
+            let keyword = self.previous();
+            return Ok(Expr::This { span: keyword.span, keyword });
+        }
+        if self.match_token(&[TokenType::IDENTIFIER]) {
+            let name = self.previous();
+            return Ok(Expr::Variable { span: name.span, name });
+        }
+        if self.match_token(&[TokenType::LEFT_PAREN]) {
+            let left_paren = self.previous();
+            let expression = self.expression()?;
+            let right_paren = self.consume(TokenType::RIGHT_PAREN, "Expect ')' after expression.")?;
+            return Ok(Expr::Grouping { expression: Box::new(expression), span: (left_paren.span.0, right_paren.span.1) });
+        }
+
+        let token = self.peek();
+        Err(self.error(&token, "Expect expression."))
+    }
+
+    fn empty_expr(&self, at: usize) -> Expr {
+        Expr::LiteralExpr { value: Literal::Nil, span: (at, at) }
+    }
+
+    fn empty_stmt(&self, at: usize) -> Stmt {
+        Stmt::Block { statements: Vec::new(), span: (at, at) }
+    }
+
+    fn match_token(&mut self, types: &[TokenType]) -> bool {
+        if types.iter().any(|t| self.check(*t)) {
+            self.advance();
+            return true;
+        }
+        false
+    }
+
+    fn check(&self, token_type: TokenType) -> bool {
+        !self.is_at_end() && self.peek().token_type == token_type
+    }
+
+    fn advance(&mut self) -> Token {
+        if !self.is_at_end() {
+            self.current += 1;
+        }
+        self.previous()
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.peek().token_type == TokenType::EOF
+    }
+
+    fn peek(&self) -> Token {
+        self.tokens[self.current].clone()
+    }
+
+    fn previous(&self) -> Token {
+        self.tokens[self.current - 1].clone()
+    }
+
+    fn consume(&mut self, token_type: TokenType, message: &str) -> Result<Token, Diagnostic> {
+        if self.check(token_type) {
+            return Ok(self.advance());
+        }
+
+        let token = self.peek();
+        Err(self.error(&token, message))
+    }
+
+    fn error(&self, token: &Token, message: &str) -> Diagnostic {
+        Diagnostic::error(message.to_string(), token.span)
+    }
+
+    // Discards tokens until we're past the offending statement, so the next
+    // `declaration()` call starts fresh at a likely statement boundary.
+    fn synchronize(&mut self) {
+        self.advance();
+
+        while !self.is_at_end() {
+            if self.previous().token_type == TokenType::SEMICOLON {
+                return;
+            }
+
+            if STATEMENT_BOUNDARIES.contains(&self.peek().token_type) {
+                return;
+            }
+
+            self.advance();
+        }
+    }
+}
+
+// Round-trips parser output by re-parenthesization (the approach syn's
+// precedence suite uses): parse a source expression, wrap every non-leaf
+// subexpression in an explicit `Grouping`, print that back out through
+// `AstPrinter`, re-parse the printed form with `parse_expr_dump`, and assert
+// the two trees are equal ignoring spans. The dump reader rebuilds whatever
+// tree was printed, so a failure means the printed grouping lost structure;
+// a parser that nests operators wrongly still produces a tree that
+// round-trips, and this comparison alone does not catch it.
+#[cfg(test)]
+mod precedence_tests {
+    use super::*;
+    use crate::ast::AstPrinter;
+    use crate::lexer::Scanner;
+
+    fn wrap(expr: Expr) -> Expr {
+        Expr::Grouping { expression: Box::new(expr), span: (0, 0) }
+    }
+
+    fn reparenthesize(expr: &Expr) -> Expr {
+        match expr {
+            Expr::LiteralExpr { .. } | Expr::Variable { .. } | Expr::This { .. } | Expr::Super { .. } => expr.clone(),
+            Expr::Grouping { expression, span } => Expr::Grouping { expression: Box::new(reparenthesize(expression)), span: *span },
+            Expr::Unary { operator, right, span } => {
+                wrap(Expr::Unary { operator: operator.clone(), right: Box::new(reparenthesize(right)), span: *span })
+            }
+            Expr::Binary { left, operator, right, span } => wrap(Expr::Binary {
+                left: Box::new(reparenthesize(left)),
+                operator: operator.clone(),
+                right: Box::new(reparenthesize(right)),
+                span: *span,
+            }),
+            Expr::Logical { left, operator, right, span } => wrap(Expr::Logical {
+                left: Box::new(reparenthesize(left)),
+                operator: operator.clone(),
+                right: Box::new(reparenthesize(right)),
+                span: *span,
+            }),
+            Expr::Call { callee, paren, arguments, span } => wrap(Expr::Call {
+                callee: Box::new(reparenthesize(callee)),
+                paren: paren.clone(),
+                arguments: arguments.iter().map(|a| Box::new(reparenthesize(a))).collect(),
+                span: *span,
+            }),
+            Expr::Get { object, name, span } => {
+                wrap(Expr::Get { object: Box::new(reparenthesize(object)), name: name.clone(), span: *span })
+            }
+            Expr::Set { object, name, value, span } => wrap(Expr::Set {
+                object: Box::new(reparenthesize(object)),
+                name: name.clone(),
+                value: Box::new(reparenthesize(value)),
+                span: *span,
+            }),
+            Expr::Assign { name, value, span } => {
+                wrap(Expr::Assign { name: name.clone(), value: Box::new(reparenthesize(value)), span: *span })
+            }
+        }
+    }
+
+    fn parse_expression(source: &str) -> Expr {
+        let tokens = Scanner::new(source.to_string()).scan_tokens();
+        let statements = Parser::new(tokens).parse().expect("corpus entries must parse");
+        match &statements[0] {
+            Stmt::Expression { expression, .. } => expression.clone(),
+            _ => panic!("expected a single expression statement"),
+        }
+    }
+
+    #[test]
+    fn precedence_survives_reparenthesization() {
+        let corpus = [
+            "1 + 2 * 3;",
+            "1 + 2 + 3;",
+            "(1 + 2) * 3;",
+            "-1 * -2;",
+            "1 < 2 == 3 >= 4;",
+            "1 + 2 and 3 or 4;",
+            "!true == false;",
+        ];
+
+        let printer = AstPrinter {};
+
+        for source in corpus {
+            let expr = parse_expression(source);
+            let reparenthesized = reparenthesize(&expr);
+            let printed = printer.to_string(&reparenthesized);
+            let reparsed = crate::ast::parse_expr_dump(printed.as_str());
+
+            assert!(reparenthesized.eq_ignore_span(&reparsed), "precedence mismatch for `{}`: printed `{}`", source, printed);
+        }
+    }
+}
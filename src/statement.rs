@@ -1,47 +1,68 @@
 use crate::expressions::Expr;
 use crate::lexer::Token;
+use define_ast_macro::define_ast;
 
 type StmtArgs = Vec<Stmt>;
 // Methods is the structure Stmt::Function
 type Methods = Vec<Stmt>;
 type FunctionParams = Vec<Token>;
 
-pub trait StmtVisitor<R> {
-    fn visit_block_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_class_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_expression_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_function_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_if_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_print_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_return_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_variable_stmt(&self, stmt: &Stmt) -> R;
-    fn visit_while_stmt(&self, stmt: &Stmt) -> R;
-}
-
-pub enum Stmt {
-    Print { expression: Expr },
-    Block { statements: StmtArgs },
-    Expression { expression: Expr },
-    While { condition: Expr, body: Box<Stmt> },
-    Return { keyword: Token, value: Expr },
-    Variable { name: Token, initializer: Expr },
-    If { condition: Expr, then_branch: Box<Stmt>, else_branch: Box<Stmt> },
-    Function { name: Token, params: FunctionParams, body: StmtArgs },
-    Class { name: Token, super_class: Expr, methods: Methods },
+define_ast! {
+    Stmt {
+        Print(expression: Expr),
+        Block(statements: StmtArgs),
+        Expression(expression: Expr),
+        While(condition: Expr, body: Box<Stmt>),
+        Return(keyword: Token, value: Expr),
+        Variable(name: Token, initializer: Expr),
+        If(condition: Expr, then_branch: Box<Stmt>, else_branch: Box<Stmt>),
+        Function(name: Token, params: FunctionParams, body: StmtArgs),
+        Class(name: Token, super_class: Expr, methods: Methods),
+    }
 }
 
 impl Stmt {
-    pub fn accept<R>(&self, visitor: &impl StmtVisitor<R>) -> R {
-        match self {
-            Stmt::Print { .. } => visitor.visit_print_stmt(self),
-            Stmt::Block { .. } => visitor.visit_block_stmt(self),
-            Stmt::Expression { .. } => visitor.visit_expression_stmt(self),
-            Stmt::While { .. } => visitor.visit_while_stmt(self),
-            Stmt::Return { .. } => visitor.visit_return_stmt(self),
-            Stmt::Variable { .. } => visitor.visit_variable_stmt(self),
-            Stmt::If { .. } => visitor.visit_if_stmt(self),
-            Stmt::Function { .. } => visitor.visit_function_stmt(self),
-            Stmt::Class { .. } => visitor.visit_class_stmt(self),
+    // Structural equality that ignores spans/lines; see `Expr::eq_ignore_span`.
+    pub fn eq_ignore_span(&self, other: &Stmt) -> bool {
+        match (self, other) {
+            (Stmt::Print { expression, .. }, Stmt::Print { expression: e2, .. }) => expression.eq_ignore_span(e2),
+            (Stmt::Block { statements, .. }, Stmt::Block { statements: s2, .. }) => {
+                statements.len() == s2.len() && statements.iter().zip(s2).all(|(a, b)| a.eq_ignore_span(b))
+            }
+            (Stmt::Expression { expression, .. }, Stmt::Expression { expression: e2, .. }) => expression.eq_ignore_span(e2),
+            (Stmt::While { condition, body, .. }, Stmt::While { condition: c2, body: b2, .. }) => {
+                condition.eq_ignore_span(c2) && body.eq_ignore_span(b2)
+            }
+            (Stmt::Return { keyword, value, .. }, Stmt::Return { keyword: k2, value: v2, .. }) => {
+                keyword.eq_ignore_span(k2) && value.eq_ignore_span(v2)
+            }
+            (Stmt::Variable { name, initializer, .. }, Stmt::Variable { name: n2, initializer: i2, .. }) => {
+                name.eq_ignore_span(n2) && initializer.eq_ignore_span(i2)
+            }
+            (
+                Stmt::If { condition, then_branch, else_branch, .. },
+                Stmt::If { condition: c2, then_branch: t2, else_branch: e2, .. },
+            ) => condition.eq_ignore_span(c2) && then_branch.eq_ignore_span(t2) && else_branch.eq_ignore_span(e2),
+            (
+                Stmt::Function { name, params, body, .. },
+                Stmt::Function { name: n2, params: p2, body: b2, .. },
+            ) => {
+                name.eq_ignore_span(n2)
+                    && params.len() == p2.len()
+                    && params.iter().zip(p2).all(|(a, b)| a.eq_ignore_span(b))
+                    && body.len() == b2.len()
+                    && body.iter().zip(b2).all(|(a, b)| a.eq_ignore_span(b))
+            }
+            (
+                Stmt::Class { name, super_class, methods, .. },
+                Stmt::Class { name: n2, super_class: s2, methods: m2, .. },
+            ) => {
+                name.eq_ignore_span(n2)
+                    && super_class.eq_ignore_span(s2)
+                    && methods.len() == m2.len()
+                    && methods.iter().zip(m2).all(|(a, b)| a.eq_ignore_span(b))
+            }
+            _ => false,
         }
     }
-}
\ No newline at end of file
+}